@@ -111,21 +111,30 @@ enum TermType {
 
 impl Term {
     // AtomVM tag constants (from AtomVM source)
-    const TERM_PRIMARY_MASK: usize = 0x3;
-    const TERM_PRIMARY_IMMED: usize = 0x3;
-    const TERM_PRIMARY_LIST: usize = 0x1;
-    const TERM_PRIMARY_BOXED: usize = 0x2;
+    pub(crate) const TERM_PRIMARY_MASK: usize = 0x3;
+    pub(crate) const TERM_PRIMARY_IMMED: usize = 0x3;
+    pub(crate) const TERM_PRIMARY_LIST: usize = 0x1;
+    pub(crate) const TERM_PRIMARY_BOXED: usize = 0x2;
     
+    // 4-bit immediate tags
     const TERM_IMMED_TAG_MASK: usize = 0xF;
     const TERM_INTEGER_TAG: usize = 0xF;
-    const TERM_ATOM_TAG: usize = 0xB;
     const TERM_PID_TAG: usize = 0x3;
     const TERM_PORT_TAG: usize = 0x7;
+    const TERM_IMMED2_TAG: usize = 0xB;
+    
+    // Atoms and nil share the IMMED2 immediate tag and are told apart by a
+    // 6-bit sub-tag; the atom sub-tag happens to equal TERM_IMMED2_TAG
+    const TERM_IMMED2_TAG_MASK: usize = 0x3F;
+    const TERM_IMMED2_TAG_SIZE: usize = 6;
+    const TERM_IMMED2_ATOM: usize = 0xB;
     
     const TERM_NIL: usize = 0x3B;
     
     const TERM_BOXED_TAG_MASK: usize = 0x3F;
-    const TERM_BOXED_TUPLE: usize = 0x00;
+    // Boxed headers keep the arity/size above the 6-bit boxed tag
+    pub(crate) const TERM_BOXED_TAG_SIZE: usize = 6;
+    pub(crate) const TERM_BOXED_TUPLE: usize = 0x00;
     const TERM_BOXED_POSITIVE_INTEGER: usize = 0x08;
     const TERM_BOXED_REF: usize = 0x10;
     const TERM_BOXED_FUN: usize = 0x18;
@@ -156,7 +165,10 @@ impl Term {
             Self::TERM_PRIMARY_IMMED => {
                 match self.0 & Self::TERM_IMMED_TAG_MASK {
                     Self::TERM_INTEGER_TAG => TermType::SmallInt,
-                    Self::TERM_ATOM_TAG => TermType::Atom,
+                    Self::TERM_IMMED2_TAG => match self.0 & Self::TERM_IMMED2_TAG_MASK {
+                        Self::TERM_IMMED2_ATOM => TermType::Atom,
+                        _ => TermType::Invalid,
+                    },
                     Self::TERM_PID_TAG => TermType::Pid,
                     Self::TERM_PORT_TAG => TermType::Port,
                     _ => TermType::Invalid,
@@ -202,7 +214,7 @@ impl Term {
 
     fn extract_atom_index(self) -> NifResult<AtomIndex> {
         match self.decode_type() {
-            TermType::Atom => Ok(AtomIndex((self.0 >> 4) as u32)),
+            TermType::Atom => Ok(AtomIndex((self.0 >> Self::TERM_IMMED2_TAG_SIZE) as u32)),
             _ => Err(NifError::BadArg),
         }
     }
@@ -212,7 +224,7 @@ impl Term {
             TermType::Tuple => {
                 let boxed_ptr = (self.0 & !Self::TERM_PRIMARY_MASK) as *const usize;
                 let header = unsafe { *boxed_ptr };
-                Ok((header >> 6) as usize)
+                Ok(header >> Self::TERM_BOXED_TAG_SIZE)
             }
             _ => Err(NifError::BadArg),
         }
@@ -305,7 +317,7 @@ impl Term {
     }
 
     fn encode_atom(AtomIndex(index): AtomIndex) -> NifResult<Self> {
-        Ok(Term(((index as usize) << 4) | Self::TERM_ATOM_TAG))
+        Ok(Term(((index as usize) << Self::TERM_IMMED2_TAG_SIZE) | Self::TERM_IMMED2_ATOM))
     }
 
     fn encode_nil() -> Self {
@@ -542,23 +554,16 @@ impl TermValue {
         self.fold_list(0, |acc, _| acc + 1)
     }
 
+    /// Iterate over list elements by reference (lazy, no allocation)
+    /// 
+    /// Stops at `Nil` or at the first non-cons tail, like `list_to_vec`.
+    pub fn iter_list(&self) -> ListIter<'_> {
+        ListIter { current: self }
+    }
+
     /// Convert list to Vec
     pub fn list_to_vec(&self) -> Vec<TermValue> {
-        let mut result = Vec::new();
-        let mut current = self;
-        
-        loop {
-            match current {
-                TermValue::Nil => break,
-                TermValue::List(head, tail) => {
-                    result.push((**head).clone());
-                    current = tail;
-                }
-                _ => break,
-            }
-        }
-        
-        result
+        self.iter_list().cloned().collect()
     }
     
     /// Get map value by key (functional lookup)
@@ -611,6 +616,28 @@ impl TermValue {
     }
 }
 
+/// Borrowing iterator over the elements of a `TermValue::List`
+/// 
+/// Walks the cons cells in place; see `TermValue::iter_list`.
+#[derive(Debug, Clone)]
+pub struct ListIter<'a> {
+    current: &'a TermValue,
+}
+
+impl<'a> Iterator for ListIter<'a> {
+    type Item = &'a TermValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.current {
+            TermValue::List(head, tail) => {
+                self.current = tail;
+                Some(head)
+            }
+            _ => None,
+        }
+    }
+}
+
 // ── Generic Smart Constructors ──────────────────────────────────────────────
 
 impl TermValue {
@@ -677,9 +704,9 @@ impl TermValue {
 
     /// Example: Sum all integers in a list
     pub fn sum_list(&self) -> i32 {
-        self.fold_list(0, |acc, elem| {
-            acc + elem.as_int().unwrap_or(0)
-        })
+        self.iter_list()
+            .map(|elem| elem.as_int().unwrap_or(0))
+            .sum()
     }
     
     /// Example: Convert list of integers to list of their doubles
@@ -220,6 +220,36 @@ pub fn large_map_fixture<T: AtomTableOps>(size: usize, table: &T) -> TermValue {
     TermValue::map(pairs)
 }
 
+/// Representative spread of term kinds for codec round-trip testing
+/// 
+/// Covers nil, small and out-of-small-range integers, atoms, flat and
+/// nested tuples, lists, binaries and floats.
+pub fn sample_terms<T: AtomTableOps>(table: &T) -> Vec<TermValue> {
+    vec![
+        TermValue::Nil,
+        TermValue::int(0),
+        TermValue::int(-1),
+        TermValue::int((1 << 27) - 1), // Largest small int
+        TermValue::int(-(1 << 27)),    // Smallest small int
+        TermValue::int(i32::MAX),
+        TermValue::int(i32::MIN),
+        TermValue::atom("ok", table),
+        TermValue::atom("sample_atom", table),
+        TermValue::tuple(vec![]),
+        TermValue::tuple(vec![TermValue::atom("ok", table), TermValue::int(1)]),
+        TermValue::tuple(vec![
+            TermValue::int(1),
+            TermValue::tuple(vec![TermValue::atom("nested", table), TermValue::Nil]),
+        ]),
+        TermValue::list(vec![TermValue::int(1), TermValue::int(2), TermValue::int(3)]),
+        TermValue::list(vec![TermValue::tuple(vec![TermValue::int(1)]), TermValue::Nil]),
+        TermValue::binary(vec![]),
+        TermValue::binary(b"sample".to_vec()),
+        TermValue::float(0.0),
+        TermValue::float(-2.5),
+    ]
+}
+
 // ── Binary Data Fixtures ───────────────────────────────────────────────────
 
 /// Binary data fixtures for different scenarios
@@ -405,7 +435,7 @@ fn permissions_fixture<T: AtomTableOps>(table: &T) -> TermValue {
 mod tests {
     use super::*;
     use crate::testing::helpers::*;
-    use crate::testing::mocks::MockAtomTable;
+    use crate::testing::mocks::{MockAtomTable, MockHeap};
 
     #[test]
     fn test_user_fixture() {
@@ -568,4 +598,46 @@ mod tests {
         assert!(matches!(worker_pid, TermValue::Pid(_)));
         assert_ne!(self_pid, worker_pid);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sample_terms() {
+        let table = MockAtomTable::new();
+        let samples = sample_terms(&table);
+        
+        // Every major kind is represented
+        assert!(samples.iter().any(|t| t.is_nil()));
+        assert!(samples.iter().any(|t| t.as_int() == Some(i32::MAX)));
+        assert!(samples.iter().any(|t| t.is_atom_str("ok", &table)));
+        assert!(samples.iter().any(|t| t.tuple_get(1).is_some_and(|e| e.tuple_arity() == 2)));
+        assert!(samples.iter().any(|t| t.as_list().is_some()));
+        assert!(samples.iter().any(|t| matches!(t, TermValue::Binary(_))));
+        assert!(samples.iter().any(|t| matches!(t, TermValue::Float(_))));
+    }
+
+    #[test]
+    fn test_sample_terms_mock_heap_roundtrip() {
+        let table = MockAtomTable::new();
+        let mut heap = MockHeap::new();
+        let samples = sample_terms(&table);
+        
+        // A codec may reject a term, but must never silently alter one
+        let mut rejected = Vec::new();
+        for sample in &samples {
+            match heap.from_value(sample.clone()) {
+                Ok(term) => assert_eq!(&heap.to_value(term).unwrap(), sample),
+                Err(_) => rejected.push(sample.clone()),
+            }
+        }
+        
+        // MockHeap has no boxed integers, binaries or floats yet; shrink this
+        // list as it gains support
+        assert_eq!(rejected, vec![
+            TermValue::int(i32::MAX),
+            TermValue::int(i32::MIN),
+            TermValue::binary(vec![]),
+            TermValue::binary(b"sample".to_vec()),
+            TermValue::float(0.0),
+            TermValue::float(-2.5),
+        ]);
+    }
+}
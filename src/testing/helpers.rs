@@ -426,4 +426,41 @@ mod tests {
         assert_map_has_key(&stats, "requests_total", &table);
         assert_map_contains(&stats, "requests_total", &TermValue::int(1000), &table);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_iter_list_sum() {
+        let list = int_list(&[1, 2, 3, 4]);
+        
+        let sum: i32 = list.iter_list().filter_map(|elem| elem.as_int()).sum();
+        assert_eq!(sum, 10);
+        assert_eq!(list.sum_list(), 10);
+        assert_eq!(list.iter_list().count(), 4);
+        
+        // Nil and non-lists yield nothing
+        assert_eq!(TermValue::Nil.iter_list().count(), 0);
+        assert_eq!(TermValue::int(5).iter_list().count(), 0);
+    }
+
+    #[test]
+    fn test_iter_list_borrows_in_place() {
+        let list = int_list(&[7, 8]);
+        let (head, tail) = list.as_list().unwrap();
+        let (second, _) = tail.as_list().unwrap();
+        
+        // Items are the cons cells' own heads, not copies
+        let items: Vec<&TermValue> = list.iter_list().collect();
+        assert!(core::ptr::eq(items[0], head));
+        assert!(core::ptr::eq(items[1], second));
+    }
+
+    #[test]
+    fn test_iter_list_improper_tail() {
+        // [1 | 2] stops at the non-list tail, matching list_to_vec
+        let improper = TermValue::List(
+            alloc::boxed::Box::new(TermValue::int(1)),
+            alloc::boxed::Box::new(TermValue::int(2)),
+        );
+        assert_eq!(improper.iter_list().count(), 1);
+        assert_eq!(improper.list_to_vec(), vec![TermValue::int(1)]);
+    }
+}
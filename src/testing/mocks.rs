@@ -12,7 +12,8 @@ extern crate alloc;
 
 use alloc::{collections::BTreeMap, string::{String, ToString}, vec::Vec, boxed::Box};
 use core::ffi::c_uint;
-use core::cell::RefCell;
+use core::cell::{RefCell, UnsafeCell};
+use core::ops::{Deref, DerefMut};
 use crate::atom::{AtomIndex, AtomTableOps, AtomError, AtomRef, EnsureAtomsOpt};
 
 // ── Mock Atom Table Implementation ─────────────────────────────────────────
@@ -351,109 +352,230 @@ impl MockResourceManagerState {
     }
 }
 
+/// Runtime borrow-checked cell for mock state mutated through `&self`
+/// 
+/// `ResourceManager` requires `Send + Sync` and mutates through `&self`,
+/// which rules out `RefCell`. This is the same idea with an atomic borrow
+/// flag: a conflicting borrow panics instead of aliasing, whether it comes
+/// from re-entrancy or from another thread.
+pub struct MockStateCell<T> {
+    borrow: AtomicUsize, // Reader count, or WRITING while mutably borrowed
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: access to `value` is only handed out through guards, and the
+// atomic borrow flag guarantees either one writer or any number of readers.
+unsafe impl<T: Send> Send for MockStateCell<T> {}
+unsafe impl<T: Send + Sync> Sync for MockStateCell<T> {}
+
+impl<T> MockStateCell<T> {
+    const WRITING: usize = usize::MAX;
+
+    pub fn new(value: T) -> Self {
+        Self {
+            borrow: AtomicUsize::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Shared borrow; panics if the state is mutably borrowed
+    pub fn borrow(&self) -> MockStateRef<'_, T> {
+        match self.try_borrow() {
+            Some(guard) => guard,
+            None => panic!("mock state already mutably borrowed"),
+        }
+    }
+
+    /// Shared borrow, or `None` if the state is mutably borrowed
+    pub fn try_borrow(&self) -> Option<MockStateRef<'_, T>> {
+        self.borrow
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |n| {
+                if n == Self::WRITING { None } else { Some(n + 1) }
+            })
+            .ok()
+            .map(|_| MockStateRef { cell: self })
+    }
+
+    /// Exclusive borrow; panics if the state is borrowed at all
+    pub fn borrow_mut(&self) -> MockStateRefMut<'_, T> {
+        if self.borrow
+            .compare_exchange(0, Self::WRITING, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            panic!("mock state already borrowed");
+        }
+        MockStateRefMut { cell: self }
+    }
+
+    /// Direct access when the cell is uniquely owned
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for MockStateCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.try_borrow() {
+            Some(guard) => f.debug_tuple("MockStateCell").field(&*guard).finish(),
+            None => f.write_str("MockStateCell(<borrowed>)"),
+        }
+    }
+}
+
+/// Shared borrow guard for [`MockStateCell`]
+pub struct MockStateRef<'a, T> {
+    cell: &'a MockStateCell<T>,
+}
+
+impl<T> Deref for MockStateRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the reader count held by this guard excludes writers
+        unsafe { &*self.cell.value.get() }
+    }
+}
+
+impl<T> Drop for MockStateRef<'_, T> {
+    fn drop(&mut self) {
+        self.cell.borrow.fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// Exclusive borrow guard for [`MockStateCell`]
+pub struct MockStateRefMut<'a, T> {
+    cell: &'a MockStateCell<T>,
+}
+
+impl<T> Deref for MockStateRefMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the WRITING flag held by this guard excludes all others
+        unsafe { &*self.cell.value.get() }
+    }
+}
+
+impl<T> DerefMut for MockStateRefMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the WRITING flag held by this guard excludes all others
+        unsafe { &mut *self.cell.value.get() }
+    }
+}
+
+impl<T> Drop for MockStateRefMut<'_, T> {
+    fn drop(&mut self) {
+        self.cell.borrow.store(0, Ordering::Release);
+    }
+}
+
 /// Mock implementation of ResourceManager for testing
 /// 
-/// Note: This is not thread-safe in no_std. For concurrent testing,
-/// external synchronization would be needed.
+/// The `ResourceManager` trait mutates through `&self`, so state lives in a
+/// `MockStateCell`. Mutation is runtime borrow-checked: an aliasing bug (e.g.
+/// a mutating call while a `get_state()` borrow is held) panics loudly
+/// instead of silently corrupting state.
 #[derive(Debug)]
 pub struct MockResourceManager {
-    pub state: MockResourceManagerState,
+    pub state: MockStateCell<MockResourceManagerState>,
 }
 
 impl MockResourceManager {
     pub fn new() -> Self {
         Self {
-            state: MockResourceManagerState::new(),
+            state: MockStateCell::new(MockResourceManagerState::new()),
         }
     }
     
     pub fn with_max_resources(mut self, max: usize) -> Self {
-        self.state.max_resources = Some(max);
+        self.state.get_mut().max_resources = Some(max);
         self
     }
     
     pub fn with_max_monitors(mut self, max: usize) -> Self {
-        self.state.max_monitors = Some(max);
+        self.state.get_mut().max_monitors = Some(max);
         self
     }
     
     // Test behavior control methods
     pub fn set_fail_init(&mut self, fail: bool) {
-        self.state.fail_init.store(fail, Ordering::SeqCst);
+        self.state.get_mut().fail_init.store(fail, Ordering::SeqCst);
     }
     
     pub fn set_fail_alloc(&mut self, fail: bool) {
-        self.state.fail_alloc.store(fail, Ordering::SeqCst);
+        self.state.get_mut().fail_alloc.store(fail, Ordering::SeqCst);
     }
     
     pub fn set_fail_make_resource(&mut self, fail: bool) {
-        self.state.fail_make_resource.store(fail, Ordering::SeqCst);
+        self.state.get_mut().fail_make_resource.store(fail, Ordering::SeqCst);
     }
     
     pub fn set_fail_get_resource(&mut self, fail: bool) {
-        self.state.fail_get_resource.store(fail, Ordering::SeqCst);
+        self.state.get_mut().fail_get_resource.store(fail, Ordering::SeqCst);
     }
     
     // State inspection methods
     pub fn get_resource_count(&self) -> usize {
-        self.state.resources.len()
+        self.state.borrow().resources.len()
     }
     
     pub fn get_resource_type_count(&self) -> usize {
-        self.state.resource_types.len()
+        self.state.borrow().resource_types.len()
     }
     
     pub fn get_monitor_count(&self) -> usize {
-        self.state.monitors.len()
+        self.state.borrow().monitors.len()
     }
     
     pub fn verify_init_called(&self, name: &str) -> bool {
-        self.state.init_calls.contains(&name.to_string())
+        self.state.borrow().init_calls.iter().any(|call| call == name)
     }
     
     pub fn verify_destructor_called(&self, resource_id: usize) -> bool {
-        self.state.destructor_calls.contains(&resource_id)
+        self.state.borrow().destructor_calls.contains(&resource_id)
     }
     
     pub fn get_resource_ref_count(&self, ptr: *mut c_void) -> Option<usize> {
-        if let Some(resource_id) = self.state.ptr_to_resource_id(ptr) {
-            self.state.resources.get(&resource_id).map(|r| r.ref_count)
-        } else {
-            None
-        }
+        let state = self.state.borrow();
+        let resource_id = state.ptr_to_resource_id(ptr)?;
+        state.resources.get(&resource_id).map(|r| r.ref_count)
     }
     
     pub fn simulate_destructor_call(&mut self, ptr: *mut c_void) {
-        if let Some(resource_id) = self.state.ptr_to_resource_id(ptr) {
-            self.state.destructor_calls.push(resource_id);
-            self.state.resources.remove(&resource_id);
+        let state = self.state.get_mut();
+        if let Some(resource_id) = state.ptr_to_resource_id(ptr) {
+            state.destructor_calls.push(resource_id);
+            state.resources.remove(&resource_id);
         }
     }
     
     pub fn reset(&mut self) {
-        self.state.reset();
+        self.state.get_mut().reset();
     }
     
     pub fn get_init_call_count(&self) -> usize {
-        self.state.init_calls.len()
+        self.state.borrow().init_calls.len()
     }
     
     pub fn get_alloc_call_count(&self) -> usize {
-        self.state.alloc_calls.len()
+        self.state.borrow().alloc_calls.len()
     }
     
     pub fn get_destructor_call_count(&self) -> usize {
-        self.state.destructor_calls.len()
+        self.state.borrow().destructor_calls.len()
     }
     
     // Public getter for state access in tests
-    pub fn get_state(&self) -> &MockResourceManagerState {
-        &self.state
+    //
+    // Holding the returned guard while calling a mutating trait method is an
+    // aliasing bug and will panic.
+    pub fn get_state(&self) -> MockStateRef<'_, MockResourceManagerState> {
+        self.state.borrow()
     }
     
     // Public mutable getter for state access in tests
     pub fn get_state_mut(&mut self) -> &mut MockResourceManagerState {
-        &mut self.state
+        self.state.get_mut()
     }
 }
 
@@ -471,7 +593,9 @@ impl ResourceManager for MockResourceManager {
         init: &ErlNifResourceTypeInit,
         _flags: ErlNifResourceFlags,
     ) -> Result<*mut ErlNifResourceType, ResourceError> {
-        if self.state.fail_init.load(Ordering::SeqCst) {
+        let state = self.state.get_mut();
+        
+        if state.fail_init.load(Ordering::SeqCst) {
             return Err(ResourceError::InitializationFailed);
         }
         
@@ -480,11 +604,11 @@ impl ResourceManager for MockResourceManager {
         }
         
         // Check if resource type already exists
-        if self.state.resource_types.contains_key(name) {
+        if state.resource_types.contains_key(name) {
             return Err(ResourceError::InitializationFailed);
         }
         
-        let type_id = self.state.generate_type_id();
+        let type_id = state.generate_type_id();
         let resource_type = MockResourceType {
             id: type_id,
             name: name.to_string(),
@@ -493,10 +617,10 @@ impl ResourceManager for MockResourceManager {
             has_down_callback: init.down.is_some(),
         };
         
-        self.state.init_calls.push(name.to_string());
-        self.state.resource_types.insert(name.to_string(), resource_type);
+        state.init_calls.push(name.to_string());
+        state.resource_types.insert(name.to_string(), resource_type);
         
-        Ok(self.state.type_id_to_ptr(type_id))
+        Ok(state.type_id_to_ptr(type_id))
     }
 
     fn alloc_resource(
@@ -504,7 +628,9 @@ impl ResourceManager for MockResourceManager {
         resource_type: *mut ErlNifResourceType,
         size: c_uint,
     ) -> Result<*mut c_void, ResourceError> {
-        if self.state.fail_alloc.load(Ordering::SeqCst) {
+        let mut state = self.state.borrow_mut();
+        
+        if state.fail_alloc.load(Ordering::SeqCst) {
             return Err(ResourceError::OutOfMemory);
         }
         
@@ -517,18 +643,18 @@ impl ResourceManager for MockResourceManager {
         }
         
         // Check resource limits
-        if let Some(max) = self.state.max_resources {
-            if self.state.resources.len() >= max {
+        if let Some(max) = state.max_resources {
+            if state.resources.len() >= max {
                 return Err(ResourceError::OutOfMemory);
             }
         }
         
-        let type_id = match self.state.ptr_to_type_id(resource_type) {
+        let type_id = match state.ptr_to_type_id(resource_type) {
             Some(id) => id,
             None => return Err(ResourceError::BadResourceType),
         };
         
-        let resource_id = self.state.generate_resource_id();
+        let resource_id = state.generate_resource_id();
         let resource = MockResource {
             id: resource_id,
             type_id,
@@ -537,15 +663,10 @@ impl ResourceManager for MockResourceManager {
             data: alloc::vec![0u8; size as usize], // Initialize with zeros
         };
         
-        // Since we have &self, we need to use unsafe to modify the state
-        // This is acceptable for testing purposes
-        unsafe {
-            let state_ptr = &self.state as *const _ as *mut MockResourceManagerState;
-            (*state_ptr).alloc_calls.push((type_id, size));
-            (*state_ptr).resources.insert(resource_id, resource);
-        }
+        state.alloc_calls.push((type_id, size));
+        state.resources.insert(resource_id, resource);
         
-        Ok(self.state.resource_id_to_ptr(resource_id))
+        Ok(state.resource_id_to_ptr(resource_id))
     }
 
     fn make_resource(
@@ -553,7 +674,9 @@ impl ResourceManager for MockResourceManager {
         _env: *mut ErlNifEnv,
         obj: *mut c_void,
     ) -> Result<ERL_NIF_TERM, ResourceError> {
-        if self.state.fail_make_resource.load(Ordering::SeqCst) {
+        let mut state = self.state.borrow_mut();
+        
+        if state.fail_make_resource.load(Ordering::SeqCst) {
             return Err(ResourceError::BadArg);
         }
         
@@ -561,24 +684,19 @@ impl ResourceManager for MockResourceManager {
             return Err(ResourceError::BadArg);
         }
         
-        let resource_id = match self.state.ptr_to_resource_id(obj) {
+        let resource_id = match state.ptr_to_resource_id(obj) {
             Some(id) => id,
             None => return Err(ResourceError::BadArg),
         };
         
         // Verify resource exists
-        if !self.state.resources.contains_key(&resource_id) {
+        if !state.resources.contains_key(&resource_id) {
             return Err(ResourceError::ResourceNotFound);
         }
         
-        let term = self.state.generate_term_id();
-        
-        // Since we have &self, use unsafe to modify state
-        unsafe {
-            let state_ptr = &self.state as *const _ as *mut MockResourceManagerState;
-            (*state_ptr).make_resource_calls.push(resource_id);
-            (*state_ptr).term_to_resource.insert(term, resource_id);
-        }
+        let term = state.generate_term_id();
+        state.make_resource_calls.push(resource_id);
+        state.term_to_resource.insert(term, resource_id);
         
         Ok(term)
     }
@@ -589,7 +707,9 @@ impl ResourceManager for MockResourceManager {
         term: ERL_NIF_TERM,
         resource_type: *mut ErlNifResourceType,
     ) -> Result<*mut c_void, ResourceError> {
-        if self.state.fail_get_resource.load(Ordering::SeqCst) {
+        let mut state = self.state.borrow_mut();
+        
+        if state.fail_get_resource.load(Ordering::SeqCst) {
             return Err(ResourceError::ResourceNotFound);
         }
         
@@ -597,37 +717,32 @@ impl ResourceManager for MockResourceManager {
             return Err(ResourceError::BadArg);
         }
         
-        let type_id = match self.state.ptr_to_type_id(resource_type) {
+        let type_id = match state.ptr_to_type_id(resource_type) {
             Some(id) => id,
             None => return Err(ResourceError::BadResourceType),
         };
         
-        // Since we have &self, use unsafe to modify state
-        unsafe {
-            let state_ptr = &self.state as *const _ as *mut MockResourceManagerState;
-            (*state_ptr).get_resource_calls.push((term, type_id));
-        }
+        state.get_resource_calls.push((term, type_id));
         
         // Look up the resource ID from the term
-        let resource_id = match self.state.term_to_resource.get(&term) {
+        let resource_id = match state.term_to_resource.get(&term) {
             Some(&id) => id,
             None => return Err(ResourceError::ResourceNotFound),
         };
         
         // Verify resource exists and has correct type
-        if let Some(resource) = self.state.resources.get(&resource_id) {
-            if resource.type_id == type_id {
-                Ok(self.state.resource_id_to_ptr(resource_id))
-            } else {
-                Err(ResourceError::ResourceNotFound)
+        match state.resources.get(&resource_id) {
+            Some(resource) if resource.type_id == type_id => {
+                Ok(state.resource_id_to_ptr(resource_id))
             }
-        } else {
-            Err(ResourceError::ResourceNotFound)
+            _ => Err(ResourceError::ResourceNotFound),
         }
     }
 
     fn keep_resource(&self, obj: *mut c_void) -> Result<(), ResourceError> {
-        if self.state.fail_keep_resource.load(Ordering::SeqCst) {
+        let mut state = self.state.borrow_mut();
+        
+        if state.fail_keep_resource.load(Ordering::SeqCst) {
             return Err(ResourceError::BadArg);
         }
         
@@ -635,26 +750,25 @@ impl ResourceManager for MockResourceManager {
             return Err(ResourceError::BadArg);
         }
         
-        let resource_id = match self.state.ptr_to_resource_id(obj) {
+        let resource_id = match state.ptr_to_resource_id(obj) {
             Some(id) => id,
             None => return Err(ResourceError::BadArg),
         };
         
-        // Since we have &self, use unsafe to modify state
-        unsafe {
-            let state_ptr = &self.state as *const _ as *mut MockResourceManagerState;
-            if let Some(resource) = (*state_ptr).resources.get_mut(&resource_id) {
+        match state.resources.get_mut(&resource_id) {
+            Some(resource) => {
                 resource.ref_count += 1;
-                (*state_ptr).keep_resource_calls.push(resource_id);
+                state.keep_resource_calls.push(resource_id);
                 Ok(())
-            } else {
-                Err(ResourceError::ResourceNotFound)
             }
+            None => Err(ResourceError::ResourceNotFound),
         }
     }
 
     fn release_resource(&self, obj: *mut c_void) -> Result<(), ResourceError> {
-        if self.state.fail_release_resource.load(Ordering::SeqCst) {
+        let mut state = self.state.borrow_mut();
+        
+        if state.fail_release_resource.load(Ordering::SeqCst) {
             return Err(ResourceError::BadArg);
         }
         
@@ -662,31 +776,31 @@ impl ResourceManager for MockResourceManager {
             return Err(ResourceError::BadArg);
         }
         
-        let resource_id = match self.state.ptr_to_resource_id(obj) {
+        let resource_id = match state.ptr_to_resource_id(obj) {
             Some(id) => id,
             None => return Err(ResourceError::BadArg),
         };
         
-        // Since we have &self, use unsafe to modify state
-        unsafe {
-            let state_ptr = &self.state as *const _ as *mut MockResourceManagerState;
-            if let Some(resource) = (*state_ptr).resources.get_mut(&resource_id) {
-                (*state_ptr).release_resource_calls.push(resource_id);
-                
+        let destroyed = match state.resources.get_mut(&resource_id) {
+            Some(resource) => {
+                // If ref count reaches 0, simulate destructor call
                 if resource.ref_count > 0 {
                     resource.ref_count -= 1;
-                    
-                    // If ref count reaches 0, simulate destructor call
-                    if resource.ref_count == 0 {
-                        (*state_ptr).destructor_calls.push(resource_id);
-                        (*state_ptr).resources.remove(&resource_id);
-                    }
+                    resource.ref_count == 0
+                } else {
+                    false
                 }
-                Ok(())
-            } else {
-                Err(ResourceError::ResourceNotFound)
             }
+            None => return Err(ResourceError::ResourceNotFound),
+        };
+        
+        state.release_resource_calls.push(resource_id);
+        if destroyed {
+            state.destructor_calls.push(resource_id);
+            state.resources.remove(&resource_id);
         }
+        
+        Ok(())
     }
 
     fn select(
@@ -698,7 +812,9 @@ impl ResourceManager for MockResourceManager {
         _pid: *const ErlNifPid,
         _reference: ERL_NIF_TERM,
     ) -> Result<(), ResourceError> {
-        if self.state.fail_select.load(Ordering::SeqCst) {
+        let mut state = self.state.borrow_mut();
+        
+        if state.fail_select.load(Ordering::SeqCst) {
             return Err(ResourceError::BadArg);
         }
         
@@ -706,20 +822,16 @@ impl ResourceManager for MockResourceManager {
             return Err(ResourceError::BadArg);
         }
         
-        let resource_id = match self.state.ptr_to_resource_id(obj) {
+        let resource_id = match state.ptr_to_resource_id(obj) {
             Some(id) => id,
             None => return Err(ResourceError::BadArg),
         };
         
-        if !self.state.resources.contains_key(&resource_id) {
+        if !state.resources.contains_key(&resource_id) {
             return Err(ResourceError::ResourceNotFound);
         }
         
-        // Since we have &self, use unsafe to modify state
-        unsafe {
-            let state_ptr = &self.state as *const _ as *mut MockResourceManagerState;
-            (*state_ptr).select_calls.push((event, mode, resource_id));
-        }
+        state.select_calls.push((event, mode, resource_id));
         
         Ok(())
     }
@@ -731,7 +843,9 @@ impl ResourceManager for MockResourceManager {
         target_pid: *const ErlNifPid,
         _mon: *mut ErlNifMonitor,
     ) -> Result<(), ResourceError> {
-        if self.state.fail_monitor.load(Ordering::SeqCst) {
+        let mut state = self.state.borrow_mut();
+        
+        if state.fail_monitor.load(Ordering::SeqCst) {
             return Err(ResourceError::BadArg);
         }
         
@@ -739,37 +853,33 @@ impl ResourceManager for MockResourceManager {
             return Err(ResourceError::BadArg);
         }
         
-        let resource_id = match self.state.ptr_to_resource_id(obj) {
+        let resource_id = match state.ptr_to_resource_id(obj) {
             Some(id) => id,
             None => return Err(ResourceError::BadArg),
         };
         
-        if !self.state.resources.contains_key(&resource_id) {
+        if !state.resources.contains_key(&resource_id) {
             return Err(ResourceError::ResourceNotFound);
         }
         
         // Check monitor limits
-        if let Some(max) = self.state.max_monitors {
-            if self.state.monitors.len() >= max {
+        if let Some(max) = state.max_monitors {
+            if state.monitors.len() >= max {
                 return Err(ResourceError::BadArg);
             }
         }
         
         let pid = unsafe { *target_pid };
         
-        // Since we have &self, use unsafe to modify state
-        unsafe {
-            let state_ptr = &self.state as *const _ as *mut MockResourceManagerState;
-            let monitor_id = (*state_ptr).generate_monitor_id();
-            let monitor = MockMonitor {
-                resource_id,
-                pid,
-                active: true,
-            };
-            
-            (*state_ptr).monitor_calls.push((resource_id, pid));
-            (*state_ptr).monitors.insert(monitor_id, monitor);
-        }
+        let monitor_id = state.generate_monitor_id();
+        let monitor = MockMonitor {
+            resource_id,
+            pid,
+            active: true,
+        };
+        
+        state.monitor_calls.push((resource_id, pid));
+        state.monitors.insert(monitor_id, monitor);
         
         Ok(())
     }
@@ -780,7 +890,9 @@ impl ResourceManager for MockResourceManager {
         obj: *mut c_void,
         _mon: *const ErlNifMonitor,
     ) -> Result<(), ResourceError> {
-        if self.state.fail_demonitor.load(Ordering::SeqCst) {
+        let mut state = self.state.borrow_mut();
+        
+        if state.fail_demonitor.load(Ordering::SeqCst) {
             return Err(ResourceError::BadArg);
         }
         
@@ -788,38 +900,247 @@ impl ResourceManager for MockResourceManager {
             return Err(ResourceError::BadArg);
         }
         
-        let resource_id = match self.state.ptr_to_resource_id(obj) {
+        let resource_id = match state.ptr_to_resource_id(obj) {
             Some(id) => id,
             None => return Err(ResourceError::BadArg),
         };
         
-        // Since we have &self, use unsafe to modify state
-        unsafe {
-            let state_ptr = &self.state as *const _ as *mut MockResourceManagerState;
-            
-            // Find and remove monitor for this resource
-            let monitor_ids: Vec<_> = (*state_ptr).monitors.iter()
-                .filter(|(_, monitor)| monitor.resource_id == resource_id)
-                .map(|(id, _)| *id)
-                .collect();
-            
-            if monitor_ids.is_empty() {
-                return Err(ResourceError::ResourceNotFound);
-            }
-            
-            for monitor_id in monitor_ids {
-                (*state_ptr).demonitor_calls.push(monitor_id);
-                (*state_ptr).monitors.remove(&monitor_id);
-            }
+        // Find and remove monitor for this resource
+        let monitor_ids: Vec<_> = state.monitors.iter()
+            .filter(|(_, monitor)| monitor.resource_id == resource_id)
+            .map(|(id, _)| *id)
+            .collect();
+        
+        if monitor_ids.is_empty() {
+            return Err(ResourceError::ResourceNotFound);
+        }
+        
+        for monitor_id in monitor_ids {
+            state.demonitor_calls.push(monitor_id);
+            state.monitors.remove(&monitor_id);
         }
         
         Ok(())
     }
 }
 
+// ── Mock Heap Implementation ───────────────────────────────────────────────
+
+use core::ptr::NonNull;
+use crate::term::{Heap, Term, TermValue, NifError, NifResult};
+
+/// A compound term allocated on the mock heap
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockHeapCell {
+    /// Cons cell: (head, tail)
+    Cons(Term, Term),
+    /// Tuple with its element handles in order
+    Tuple(Vec<Term>),
+}
+
+impl MockHeapCell {
+    /// Size in bytes the cell would occupy on a real AtomVM heap
+    pub fn size_bytes(&self) -> usize {
+        let words = match self {
+            MockHeapCell::Cons(_, _) => 2,
+            MockHeapCell::Tuple(elements) => 1 + elements.len(), // Header + elements
+        };
+        words * core::mem::size_of::<usize>()
+    }
+
+    /// Lay the cell out as AtomVM heap words
+    fn to_words(&self) -> Box<[usize]> {
+        match self {
+            MockHeapCell::Cons(head, tail) => Box::new([head.raw(), tail.raw()]),
+            MockHeapCell::Tuple(elements) => {
+                let header = (elements.len() << Term::TERM_BOXED_TAG_SIZE) | Term::TERM_BOXED_TUPLE;
+                core::iter::once(header)
+                    .chain(elements.iter().map(|elem| elem.raw()))
+                    .collect()
+            }
+        }
+    }
+
+    /// Read a cell back from its AtomVM heap words
+    fn from_words(handle: usize, words: &[usize]) -> Self {
+        if handle & Term::TERM_PRIMARY_MASK == Term::TERM_PRIMARY_LIST {
+            MockHeapCell::Cons(Term::from_raw(words[0]), Term::from_raw(words[1]))
+        } else {
+            MockHeapCell::Tuple(words[1..].iter().map(|&word| Term::from_raw(word)).collect())
+        }
+    }
+}
+
+/// Mock implementation of the AtomVM heap for testing
+///
+/// The real `Term::from_value` writes compound terms (lists, tuples) into
+/// VM-owned memory, which is not available in unit tests. This mock lays each
+/// cell out in its own word storage using the AtomVM layout (a cons cell is
+/// head then tail; a tuple is an `arity << 6` header followed by its elements)
+/// and hands out the tagged address as the `Term`. Both `MockHeap::to_value`
+/// and the real `Term::to_value` can therefore decode the result. Immediates
+/// (small integers, atoms, nil) are encoded by `Term::from_value` itself.
+///
+/// `MockHeap::to_value` checks every handle against the live cells and reports
+/// `InvalidTerm` for freed ones. Freed storage is retained until the heap is
+/// dropped, so a stale handle passed to `Term::to_value` reads outdated but
+/// valid memory. Handles must not outlive the heap.
+#[derive(Debug)]
+pub struct MockHeap {
+    // Raw pointers from `Box::into_raw`, reclaimed only in `Drop`, so handles
+    // derived from them stay valid for the heap's lifetime
+    cells: BTreeMap<usize, NonNull<[usize]>>,
+    retired: Vec<NonNull<[usize]>>,
+    allocated: usize,
+    peak_allocated: usize,
+}
+
+impl MockHeap {
+    /// Create an empty mock heap
+    pub fn new() -> Self {
+        Self {
+            cells: BTreeMap::new(),
+            retired: Vec::new(),
+            allocated: 0,
+            peak_allocated: 0,
+        }
+    }
+
+    /// Encode a `TermValue`, allocating cells for compound terms
+    pub fn from_value(&mut self, value: TermValue) -> NifResult<Term> {
+        match value {
+            // Immediates never touch the heap, so the real encoder is safe here
+            value @ (TermValue::SmallInt(_) | TermValue::Atom(_) | TermValue::Nil) => {
+                Term::from_value(value, &mut Heap { _private: [] })
+            }
+            TermValue::List(head, tail) => {
+                let head_term = self.from_value(*head)?;
+                let tail_term = self.from_value(*tail)?;
+                Ok(self.alloc(MockHeapCell::Cons(head_term, tail_term), Term::TERM_PRIMARY_LIST))
+            }
+            TermValue::Tuple(elements) => {
+                let element_terms = elements
+                    .into_iter()
+                    .map(|elem| self.from_value(elem))
+                    .collect::<NifResult<Vec<Term>>>()?;
+                Ok(self.alloc(MockHeapCell::Tuple(element_terms), Term::TERM_PRIMARY_BOXED))
+            }
+            _ => Err(NifError::Other("term type not supported by MockHeap")),
+        }
+    }
+
+    /// Decode a term previously produced by this heap
+    pub fn to_value(&self, term: Term) -> NifResult<TermValue> {
+        match self.get_cell(term) {
+            Some(MockHeapCell::Cons(head, tail)) => Ok(TermValue::List(
+                Box::new(self.to_value(head)?),
+                Box::new(self.to_value(tail)?),
+            )),
+            Some(MockHeapCell::Tuple(elements)) => {
+                let values = elements
+                    .into_iter()
+                    .map(|elem| self.to_value(elem))
+                    .collect::<NifResult<Vec<TermValue>>>()?;
+                Ok(TermValue::Tuple(values))
+            }
+            // Immediates carry no pointer, so the real decoder is safe here
+            None if term.raw() & Term::TERM_PRIMARY_MASK == Term::TERM_PRIMARY_IMMED => term.to_value(),
+            None => Err(NifError::InvalidTerm),
+        }
+    }
+
+    /// Encode an atom by name, resolving it through the given table
+    ///
+    /// Using the same table for encoding and for `is_atom_str` checks keeps
+    /// atoms like `true`/`false` consistent across the round-trip.
+    pub fn atom<T: AtomTableOps>(&mut self, name: &str, table: &T) -> NifResult<Term> {
+        let index = table.ensure_atom_str(name).map_err(|_| NifError::BadArg)?;
+        self.from_value(TermValue::Atom(index))
+    }
+
+    /// Look up the cell behind a handle
+    pub fn get_cell(&self, term: Term) -> Option<MockHeapCell> {
+        self.cells
+            .get(&term.raw())
+            // SAFETY: cells are owned by the heap and only freed in `Drop`
+            .map(|words| MockHeapCell::from_words(term.raw(), unsafe { words.as_ref() }))
+    }
+
+    /// Number of live cells on the heap
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Bytes currently allocated by live cells
+    pub fn allocated(&self) -> usize {
+        self.allocated
+    }
+
+    /// High-water mark of allocated bytes since creation or `reset_peak`
+    pub fn peak(&self) -> usize {
+        self.peak_allocated
+    }
+
+    /// Restart peak tracking from the current usage (e.g. at a hop boundary)
+    pub fn reset_peak(&mut self) {
+        self.peak_allocated = self.allocated;
+    }
+
+    /// Free a single cell (not its children); returns false for unknown handles
+    pub fn free(&mut self, term: Term) -> bool {
+        match self.cells.remove(&term.raw()) {
+            Some(words) => {
+                self.allocated -= words.len() * core::mem::size_of::<usize>();
+                self.retired.push(words);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop all cells; outstanding handles become invalid
+    ///
+    /// The peak is kept so a hop that builds and then discards terms still
+    /// reports its high-water mark.
+    pub fn clear(&mut self) {
+        let cells = core::mem::take(&mut self.cells);
+        self.retired.extend(cells.into_values());
+        self.allocated = 0;
+    }
+
+    fn alloc(&mut self, cell: MockHeapCell, tag: usize) -> Term {
+        // The heap owns the storage through this raw pointer only; no `Box`
+        // is moved after the handle is derived. Word alignment leaves the low
+        // tag bits free.
+        // SAFETY: `Box::into_raw` never returns null
+        let words = unsafe { NonNull::new_unchecked(Box::into_raw(cell.to_words())) };
+        let handle = (words.cast::<usize>().as_ptr() as usize) | tag;
+        self.allocated += cell.size_bytes();
+        self.peak_allocated = self.peak_allocated.max(self.allocated);
+        self.cells.insert(handle, words);
+        Term::from_raw(handle)
+    }
+}
+
+impl Default for MockHeap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MockHeap {
+    fn drop(&mut self) {
+        for words in self.cells.values().chain(self.retired.iter()) {
+            // SAFETY: every pointer came from `Box::into_raw` in `alloc` and is
+            // held exactly once across `cells` and `retired`
+            drop(unsafe { Box::from_raw(words.as_ptr()) });
+        }
+    }
+}
+
 // ── Additional Mock Implementations ────────────────────────────────────────
 
-// Future: Add MockContext, MockHeap, etc. here as needed
+// Future: Add MockContext, etc. here as needed
 
 #[cfg(test)]
 mod tests {
@@ -1035,8 +1356,8 @@ mod tests {
             .with_max_resources(5)
             .with_max_monitors(3);
         
-        assert_eq!(manager.state.max_resources, Some(5));
-        assert_eq!(manager.state.max_monitors, Some(3));
+        assert_eq!(manager.get_state().max_resources, Some(5));
+        assert_eq!(manager.get_state().max_monitors, Some(3));
     }
 
     #[test]
@@ -1045,16 +1366,16 @@ mod tests {
         
         // Test that failure flags work
         manager.set_fail_init(true);
-        assert!(manager.state.fail_init.load(Ordering::SeqCst));
+        assert!(manager.get_state().fail_init.load(Ordering::SeqCst));
         
         manager.set_fail_alloc(true);
-        assert!(manager.state.fail_alloc.load(Ordering::SeqCst));
+        assert!(manager.get_state().fail_alloc.load(Ordering::SeqCst));
         
         manager.set_fail_make_resource(true);
-        assert!(manager.state.fail_make_resource.load(Ordering::SeqCst));
+        assert!(manager.get_state().fail_make_resource.load(Ordering::SeqCst));
         
         manager.set_fail_get_resource(true);
-        assert!(manager.state.fail_get_resource.load(Ordering::SeqCst));
+        assert!(manager.get_state().fail_get_resource.load(Ordering::SeqCst));
     }
 
     #[test]
@@ -1068,13 +1389,89 @@ mod tests {
         
         // Test that state can be reset
         let mut manager = manager;
-        manager.state.init_calls.push("test".to_string());
+        manager.get_state_mut().init_calls.push("test".to_string());
         assert_eq!(manager.get_init_call_count(), 1);
         
         manager.reset();
         assert_eq!(manager.get_init_call_count(), 0);
     }
 
+    #[test]
+    fn test_mock_resource_manager_shared_borrows() {
+        let manager = MockResourceManager::new();
+        
+        // Read-only accessors can run while a state guard is held
+        let state = manager.get_state();
+        assert_eq!(manager.get_resource_count(), 0);
+        assert_eq!(state.resources.len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "mock state already borrowed")]
+    fn test_mock_resource_manager_aliased_mutation_panics() {
+        let mut manager = MockResourceManager::new();
+        let resource_type = manager.init_resource_type(
+            core::ptr::null_mut(),
+            "aliased",
+            &resource_type_init(),
+            ErlNifResourceFlags::ERL_NIF_RT_CREATE,
+        ).unwrap();
+        
+        // Mutating through &self while a borrow is live used to be silent UB;
+        // it must now panic instead
+        let _state = manager.get_state();
+        let _ = manager.alloc_resource(resource_type, 16);
+    }
+
+    #[test]
+    fn test_mock_state_cell_shared_guards_coexist() {
+        let cell = MockStateCell::new(5);
+        
+        let a = cell.borrow();
+        let b = cell.borrow();
+        assert_eq!(*a + *b, 10);
+        assert!(cell.try_borrow().is_some());
+        drop(a);
+        drop(b);
+        
+        // Once all readers are gone a writer may borrow
+        *cell.borrow_mut() += 1;
+        assert_eq!(*cell.borrow(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "mock state already borrowed")]
+    fn test_mock_state_cell_mutable_while_shared_panics() {
+        let cell = MockStateCell::new(0);
+        let _a = cell.borrow();
+        let _b = cell.borrow();
+        let _ = cell.borrow_mut();
+    }
+
+    #[test]
+    #[should_panic(expected = "mock state already mutably borrowed")]
+    fn test_mock_state_cell_shared_while_mutable_panics() {
+        let cell = MockStateCell::new(0);
+        let _guard = cell.borrow_mut();
+        let _ = cell.borrow();
+    }
+
+    #[test]
+    fn test_mock_state_cell_debug_while_mutably_borrowed() {
+        let cell = MockStateCell::new(7);
+        assert_eq!(alloc::format!("{:?}", cell), "MockStateCell(7)");
+        
+        let _guard = cell.borrow_mut();
+        assert!(cell.try_borrow().is_none());
+        assert_eq!(alloc::format!("{:?}", cell), "MockStateCell(<borrowed>)");
+    }
+
+    #[test]
+    fn test_mock_resource_manager_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<MockResourceManager>();
+    }
+
     #[test]
     fn test_mock_resource_manager_pointer_conversion() {
         let state = MockResourceManagerState::new();
@@ -1100,4 +1497,238 @@ mod tests {
         let invalid_type_ptr = 0x5000 as *mut ErlNifResourceType;
         assert_eq!(state.ptr_to_type_id(invalid_type_ptr), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_mock_heap_immediates() {
+        let mut heap = MockHeap::new();
+
+        let int_term = heap.from_value(TermValue::int(-42)).unwrap();
+        assert_eq!(heap.to_value(int_term).unwrap(), TermValue::int(-42));
+
+        let nil_term = heap.from_value(TermValue::Nil).unwrap();
+        assert_eq!(nil_term.raw(), 0x3B);
+        assert_eq!(heap.to_value(nil_term).unwrap(), TermValue::Nil);
+
+        // Immediates never allocate
+        assert_eq!(heap.cell_count(), 0);
+    }
+
+    #[test]
+    fn test_mock_heap_list_roundtrip() {
+        let mut heap = MockHeap::new();
+        let list = TermValue::list(alloc::vec![
+            TermValue::int(1),
+            TermValue::int(2),
+            TermValue::int(3),
+        ]);
+
+        let term = heap.from_value(list.clone()).unwrap();
+        assert_eq!(heap.cell_count(), 3);
+        assert!(matches!(heap.get_cell(term), Some(MockHeapCell::Cons(_, _))));
+
+        let decoded = heap.to_value(term).unwrap();
+        assert_eq!(decoded, list);
+        assert_eq!(decoded.sum_list(), 6);
+    }
+
+    #[test]
+    fn test_mock_heap_handles_are_unique() {
+        let mut heap = MockHeap::new();
+        let a = heap.from_value(TermValue::list(alloc::vec![TermValue::int(1)])).unwrap();
+        let b = heap.from_value(TermValue::list(alloc::vec![TermValue::int(1)])).unwrap();
+
+        assert_ne!(a, b);
+        assert_eq!(heap.to_value(a).unwrap(), heap.to_value(b).unwrap());
+    }
+
+    #[test]
+    fn test_mock_heap_tuple_roundtrip() {
+        let mut heap = MockHeap::new();
+        let tuple = TermValue::tuple(alloc::vec![TermValue::int(1), TermValue::int(2)]);
+
+        let term = heap.from_value(tuple.clone()).unwrap();
+        assert_eq!(term.raw() & 0x3, 0x2); // Boxed handle
+        let decoded = heap.to_value(term).unwrap();
+        assert_eq!(decoded, tuple);
+        assert_eq!(decoded.as_tuple().unwrap(), &[TermValue::int(1), TermValue::int(2)]);
+
+        // tuple_to_list over the decoded value
+        let as_list = TermValue::list(decoded.as_tuple().unwrap().to_vec());
+        assert_eq!(as_list.list_to_vec(), alloc::vec![TermValue::int(1), TermValue::int(2)]);
+    }
+
+    #[test]
+    fn test_mock_heap_single_and_empty_tuple() {
+        let mut heap = MockHeap::new();
+
+        let single = TermValue::tuple(alloc::vec![TermValue::int(9)]);
+        let term = heap.from_value(single.clone()).unwrap();
+        assert_eq!(heap.to_value(term).unwrap(), single);
+
+        let empty = TermValue::tuple(Vec::new());
+        let term = heap.from_value(empty.clone()).unwrap();
+        assert_eq!(heap.to_value(term).unwrap().tuple_arity(), 0);
+    }
+
+    #[test]
+    fn test_mock_heap_nested_tuple_in_list() {
+        let mut heap = MockHeap::new();
+        let nested = TermValue::list(alloc::vec![
+            TermValue::tuple(alloc::vec![TermValue::int(1), TermValue::Nil]),
+            TermValue::tuple(alloc::vec![
+                TermValue::int(2),
+                TermValue::list(alloc::vec![TermValue::int(3)]),
+            ]),
+        ]);
+
+        let term = heap.from_value(nested.clone()).unwrap();
+        let decoded = heap.to_value(term).unwrap();
+        assert_eq!(decoded, nested);
+
+        let second = &decoded.list_to_vec()[1];
+        assert_eq!(second.tuple_get(1).unwrap().sum_list(), 3);
+    }
+
+    #[test]
+    fn test_mock_heap_terms_decode_with_real_decoder() {
+        let mut heap = MockHeap::new();
+        let value = TermValue::list(alloc::vec![
+            TermValue::int(1),
+            TermValue::tuple(alloc::vec![TermValue::int(2), TermValue::Nil]),
+            TermValue::tuple(Vec::new()),
+        ]);
+
+        // Term::to_value dereferences the handles, so they must point at real words
+        let term = heap.from_value(value.clone()).unwrap();
+        assert_eq!(term.to_value().unwrap(), value);
+        assert_eq!(term.to_value().unwrap(), heap.to_value(term).unwrap());
+
+        let tuple = heap.from_value(TermValue::tuple(alloc::vec![TermValue::int(7)])).unwrap();
+        assert_eq!(tuple.to_value().unwrap(), TermValue::tuple(alloc::vec![TermValue::int(7)]));
+    }
+
+    #[test]
+    fn test_mock_heap_atom_roundtrip() {
+        let mut heap = MockHeap::new();
+        let table = MockAtomTable::new();
+
+        let index = table.ensure_atom_str("hello").unwrap();
+        let term = heap.from_value(TermValue::Atom(index)).unwrap();
+        let decoded = heap.to_value(term).unwrap();
+        assert_eq!(decoded.as_atom(), Some(index));
+        assert!(decoded.is_atom_str("hello", &table));
+
+        // Large indices survive too
+        let term = heap.from_value(TermValue::Atom(AtomIndex(0x00ab_cdef))).unwrap();
+        assert_eq!(heap.to_value(term).unwrap(), TermValue::Atom(AtomIndex(0x00ab_cdef)));
+        assert_eq!(heap.cell_count(), 0);
+    }
+
+    #[test]
+    fn test_mock_heap_atom_does_not_alias_nil() {
+        let mut heap = MockHeap::new();
+
+        // Index 3 used to encode as 0x3B, which is the nil term
+        let term = heap.from_value(TermValue::Atom(AtomIndex(3))).unwrap();
+        assert_ne!(term.raw(), 0x3B);
+        assert_eq!(heap.to_value(term).unwrap(), TermValue::Atom(AtomIndex(3)));
+    }
+
+    #[test]
+    fn test_term_encode_atom_does_not_alias_nil() {
+        // Go through the production encoder, not MockHeap
+        let atom = Term::from_value(TermValue::Atom(AtomIndex(3)), &mut Heap { _private: [] }).unwrap();
+        let nil = Term::from_value(TermValue::Nil, &mut Heap { _private: [] }).unwrap();
+
+        assert_ne!(atom.raw(), 0x3B);
+        assert_ne!(atom, nil);
+        assert_eq!(atom.to_value().unwrap(), TermValue::Atom(AtomIndex(3)));
+        assert_eq!(nil.to_value().unwrap(), TermValue::Nil);
+    }
+
+    #[test]
+    fn test_mock_heap_boolean_atoms() {
+        let mut heap = MockHeap::new();
+        let table = MockAtomTable::new();
+
+        // e.g. the reply of an is_even NIF
+        let is_even = |n: i32| if n % 2 == 0 { "true" } else { "false" };
+        let true_term = heap.atom(is_even(4), &table).unwrap();
+        let false_term = heap.atom(is_even(7), &table).unwrap();
+
+        assert!(heap.to_value(true_term).unwrap().is_atom_str("true", &table));
+        assert!(heap.to_value(false_term).unwrap().is_atom_str("false", &table));
+        assert_ne!(true_term, false_term);
+
+        // Atoms nested inside compound terms keep their index
+        let reply = TermValue::tuple(alloc::vec![
+            TermValue::atom("ok", &table),
+            TermValue::atom("true", &table),
+        ]);
+        let term = heap.from_value(reply.clone()).unwrap();
+        assert_eq!(heap.to_value(term).unwrap(), reply);
+    }
+
+    #[test]
+    fn test_mock_heap_peak_tracking() {
+        let mut heap = MockHeap::new();
+        let word = core::mem::size_of::<usize>();
+        assert_eq!(heap.peak(), 0);
+
+        // Three cons cells of two words each
+        let list = heap.from_value(TermValue::list(alloc::vec![
+            TermValue::int(1),
+            TermValue::int(2),
+            TermValue::int(3),
+        ])).unwrap();
+        assert_eq!(heap.allocated(), 6 * word);
+
+        // A 2-tuple: header plus two elements
+        let tuple = heap.from_value(TermValue::tuple(alloc::vec![
+            TermValue::int(1),
+            TermValue::int(2),
+        ])).unwrap();
+        assert_eq!(heap.allocated(), 9 * word);
+        assert_eq!(heap.peak(), 9 * word);
+
+        // Freeing drops current usage but keeps the high-water mark
+        assert!(heap.free(tuple));
+        assert!(!heap.free(tuple));
+        assert_eq!(heap.allocated(), 6 * word);
+        assert_eq!(heap.peak(), 9 * word);
+
+        heap.clear();
+        assert_eq!(heap.allocated(), 0);
+        assert_eq!(heap.peak(), 9 * word);
+        assert_eq!(heap.to_value(list), Err(NifError::InvalidTerm));
+    }
+
+    #[test]
+    fn test_mock_heap_reset_peak() {
+        let mut heap = MockHeap::new();
+        let word = core::mem::size_of::<usize>();
+
+        let big = heap.from_value(TermValue::tuple(alloc::vec![TermValue::Nil; 7])).unwrap();
+        heap.free(big);
+        assert_eq!(heap.peak(), 8 * word);
+
+        heap.reset_peak();
+        assert_eq!(heap.peak(), 0);
+
+        heap.from_value(TermValue::list(alloc::vec![TermValue::int(1)])).unwrap();
+        assert_eq!(heap.peak(), 2 * word);
+    }
+
+    #[test]
+    fn test_mock_heap_invalid_handles() {
+        let mut heap = MockHeap::new();
+        let term = heap.from_value(TermValue::list(alloc::vec![TermValue::int(7)])).unwrap();
+
+        heap.clear();
+        assert_eq!(heap.to_value(term), Err(NifError::InvalidTerm));
+
+        // Unsupported term kinds are rejected rather than faked
+        let result = heap.from_value(TermValue::float(1.5));
+        assert!(result.is_err());
+    }
+}
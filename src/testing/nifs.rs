@@ -85,6 +85,14 @@ impl NifCallSimulator {
                 let length = args[0].list_length();
                 Ok(TermValue::int(length as i32))
             }
+            "sum_args" => {
+                // Variadic: sums every argument directly, no list wrapping
+                let sum = args.iter().try_fold(0i32, |acc, arg| {
+                    let value = arg.as_int().ok_or(NifError::BadArg)?;
+                    acc.checked_add(value).ok_or(NifError::Other("integer overflow"))
+                })?;
+                Ok(TermValue::int(sum))
+            }
             "make_tuple" => {
                 Ok(TermValue::tuple(args))
             }
@@ -195,6 +203,41 @@ mod tests {
         assert_eq!(simulator.last_function.as_ref().unwrap(), "list_length");
     }
 
+    #[test]
+    fn test_nif_call_simulator_sum_args() {
+        let mut simulator = NifCallSimulator::new();
+
+        // Zero arguments sum to 0
+        let result = simulator.simulate_call("sum_args", vec![]).unwrap();
+        assert_eq!(result, TermValue::int(0));
+
+        // Mixed positive and negative arguments
+        let args = vec![TermValue::int(10), TermValue::int(-3), TermValue::int(5)];
+        let result = simulator.simulate_call("sum_args", args).unwrap();
+        assert_eq!(result, TermValue::int(12));
+        assert_eq!(simulator.last_args.len(), 3);
+    }
+
+    #[test]
+    fn test_nif_call_simulator_sum_args_errors() {
+        let mut simulator = NifCallSimulator::new();
+        let atom_table = MockAtomTable::new();
+
+        // One bad argument in the middle rejects the whole call
+        let args = vec![
+            TermValue::int(1),
+            TermValue::atom("oops", &atom_table),
+            TermValue::int(2),
+        ];
+        let result = simulator.simulate_call("sum_args", args);
+        assert_eq!(result, Err(NifError::BadArg));
+
+        // Overflow is reported rather than wrapping
+        let args = vec![TermValue::int(i32::MAX), TermValue::int(1)];
+        let result = simulator.simulate_call("sum_args", args);
+        assert_eq!(result, Err(NifError::Other("integer overflow")));
+    }
+
     #[test]
     fn test_nif_call_simulator_make_tuple() {
         let mut simulator = NifCallSimulator::new();